@@ -0,0 +1,58 @@
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::OpenOptions;
+
+/// The default decryption key used by keyed ZyAura firmware.
+pub const DEFAULT_KEY: [u8; 8] = [0x62, 0xea, 0x1d, 0x4f, 0x14, 0xfa, 0xe5, 0x6c];
+
+/// Startup settings, typically loaded from a TOML/YAML file with `serde`.
+///
+/// Every field has a sensible fallback, so an empty file — or no file at all
+/// (see [`Config::default`]) — yields the same behaviour as the built-in
+/// defaults.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Decryption key for keyed firmware.
+    pub key: [u8; 8],
+
+    /// Read timeout in seconds. `None` keeps the built-in default.
+    pub timeout: Option<u64>,
+
+    /// Emit debug output.
+    pub debug: bool,
+
+    /// Telegraf `host:port` endpoint. `None` disables delivery.
+    pub telegraf: Option<String>,
+
+    /// Which [`AirQulityEvent`][crate::AirQulityEvent] variants to forward,
+    /// by name (e.g. `"AmbientTemperature"`). Empty means forward all.
+    pub forward: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            key: DEFAULT_KEY,
+            timeout: Some(5),
+            debug: false,
+            telegraf: None,
+            forward: Vec::new(),
+        }
+    }
+}
+
+impl OpenOptions {
+    /// Build [`OpenOptions`] from a loaded [`Config`].
+    pub fn from_config(config: &Config) -> Self {
+        let mut options = OpenOptions::new();
+        options
+            .with_key(config.key)
+            .debug(config.debug)
+            .timeout(config.timeout.map(Duration::from_secs));
+        options
+    }
+}
@@ -0,0 +1,69 @@
+
+use embedded_graphics::mono_font::ascii::FONT_6X10;
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::Text;
+
+use crate::AirQulityEvent;
+
+/// Keeps the most recent value per channel and renders them onto a small
+/// monochrome OLED (SSD1306/SH1106) via `embedded-graphics`.
+///
+/// Feed events from the sensor through [`Dashboard::update`] and redraw with
+/// [`Dashboard::draw`] on each new reading.
+#[derive(Debug, Default, Clone)]
+pub struct Dashboard {
+    co2: Option<u16>,      /* Relative CO2 concentration (ppm) */
+    temperature: Option<f64>, /* Ambient temperature (°C)        */
+    humidity: Option<f64>, /* Relative humidity (%)            */
+}
+
+impl Dashboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the latest value carried by an [`AirQulityEvent`]. Events that
+    /// do not carry a channel value are ignored.
+    pub fn update(&mut self, event: &AirQulityEvent) {
+        match *event {
+            AirQulityEvent::RelativeConcentration { value } => self.co2 = Some(value),
+            AirQulityEvent::AmbientTemperature { temp } => self.temperature = Some(temp),
+            AirQulityEvent::Humidity { value } => self.humidity = Some(value),
+            _ => {}
+        }
+    }
+
+    /// Render the current readings onto `target`, one channel per line.
+    ///
+    /// Works over any `I2C`/`SPI` display driver whose `DrawTarget` uses
+    /// [`BinaryColor`].
+    pub fn draw<D>(&self, target: &mut D) -> core::result::Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        target.clear(BinaryColor::Off)?;
+
+        let style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+
+        let co2 = match self.co2 {
+            Some(value) => format!("CO2:  {} ppm", value),
+            None => String::from("CO2:  --"),
+        };
+        let temp = match self.temperature {
+            Some(value) => format!("Temp: {:.1} C", value),
+            None => String::from("Temp: --"),
+        };
+        let humidity = match self.humidity {
+            Some(value) => format!("Hum:  {:.1} %", value),
+            None => String::from("Hum:  --"),
+        };
+
+        Text::new(&co2, Point::new(0, 10), style).draw(target)?;
+        Text::new(&temp, Point::new(0, 22), style).draw(target)?;
+        Text::new(&humidity, Point::new(0, 34), style).draw(target)?;
+
+        Ok(())
+    }
+}
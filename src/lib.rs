@@ -7,11 +7,21 @@ use hidapi::HidApi;
 use hidapi::HidDevice;
 
 mod error;
+mod config;
+
+#[cfg(feature = "stream")]
+mod stream;
+
+#[cfg(feature = "display")]
+mod display;
 
 const CODE_HUMD : u8 = 0x41; /* Humidity                      */
 const CODE_TAMB : u8 = 0x42; /* Ambient Temperature           */
 const CODE_CNTR : u8 = 0x50; /* Relative Concentration of CO2 */
 
+const VID: u16 = 0x04d9; /* ZyAura vendor identifier  */
+const PID: u16 = 0xa052; /* CO2 monitor product id    */
+
 fn decode_humidity(w: u16) -> f64 {
     w as f64 / 100.0
 }
@@ -22,22 +32,51 @@ fn decode_temperature(w: u16) -> f64 {
 
 fn dump(raw: &[u8; 8]) {
     debug!("--- raw ---");
-    for i in 0..8 {
-        debug!("0x{:02x} ", raw[i]);
+    for byte in raw.iter() {
+        debug!("0x{:02x} ", byte);
     }
     debug!("------");
 }
 
 pub use error::Error;
+pub use config::Config;
+
+#[cfg(feature = "stream")]
+pub use stream::AirQualityStream;
+
+#[cfg(feature = "display")]
+pub use display::Dashboard;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Decode scheme applied to the raw 8-byte HID frame.
+///
+/// Overrides the release-number heuristic used by [`Scheme::Auto`]. Different
+/// MT-8057/ZyAura revisions scramble the frame differently; picking the wrong
+/// transform surfaces as [`AirQulityEvent::ChecksumError`] or
+/// [`AirQulityEvent::UnexpectedData`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// Select between [`Scheme::KeyedZyAura`] and [`Scheme::Raw`] from the
+    /// device release number (the historical behaviour).
+    Auto,
+    /// Key XOR followed by the `"Htemp99e"` magic-table subtraction.
+    KeyedZyAura,
+    /// Byte permutation only — no key XOR, no magic subtraction. Used by the
+    /// zero-key firmware permutation.
+    PermutationOnly,
+    /// No transform; the device reports plaintext frames.
+    Raw,
+}
+
 pub struct Sensor {
-    dev: HidDevice,            /* USB device hander */
-    debug: bool,               /* Debug packet      */
-    decode: bool,              /* Use decode        */
-    key: [u8; 8],              /* Key               */
-    timeout: Option<Duration>, /* Timeout           */
+    dev: HidDevice,            /* USB device hander    */
+    debug: bool,               /* Debug packet         */
+    scheme: Scheme,            /* Decode scheme        */
+    key: [u8; 8],              /* Key                  */
+    timeout: Option<Duration>, /* Timeout              */
+    reconnect: bool,           /* Reopen on read error */
+    retry_interval: Duration,  /* Backoff between tries */
 }
 
 #[derive(Debug)]
@@ -74,33 +113,73 @@ fn decrypt(mut data: [u8; 8], key: [u8; 8]) -> [u8; 8] {
     data[0] = tmp | (data[0] >> 3);
 
     for (r, m) in data.iter_mut().zip(b"Htemp99e".iter()) {
-        *r = r.wrapping_sub(m << 4 | m >> 4);
+        *r = r.wrapping_sub(m.rotate_right(4));
     }
 
     data
 }
 
+/// Permutation-only descrambling used by the zero-key firmware revision: the
+/// same byte reorder and 3-bit right rotation as [`decrypt`] but without the key
+/// XOR or the `"Htemp99e"` magic-table subtraction.
+fn permutation_decrypt(mut data: [u8; 8]) -> [u8; 8] {
+
+    data.swap(0, 2);
+    data.swap(1, 4);
+    data.swap(3, 7);
+    data.swap(5, 6);
+
+    let tmp : u8 = data[7] << 5;
+    data[7] = (data[6] << 5) | (data[7] >> 3);
+    data[6] = (data[5] << 5) | (data[6] >> 3);
+    data[5] = (data[4] << 5) | (data[5] >> 3);
+    data[4] = (data[3] << 5) | (data[4] >> 3);
+    data[3] = (data[2] << 5) | (data[3] >> 3);
+    data[2] = (data[1] << 5) | (data[2] >> 3);
+    data[1] = (data[0] << 5) | (data[1] >> 3);
+    data[0] = tmp | (data[0] >> 3);
+
+    data
+}
+
 
 impl Sensor {
 
     fn open(options: &OpenOptions) -> Result<Self> {
-        let hidapi = HidApi::new()?;
+        let key = options.key;
+
+        let (device, scheme) = Self::open_device(key, options.scheme)?;
 
-        const VID: u16 = 0x04d9;
-        const PID: u16 = 0xa052;
+        Ok(Self {
+            dev: device,
+            debug: options.debug,
+            scheme,
+            key,
+            timeout: options.timeout,
+            reconnect: options.reconnect,
+            retry_interval: options.retry_interval,
+        })
+    }
+
+    /// Enumerate the CO2 monitor, open it and send the key feature-report
+    /// frame. Returns the open handle together with the decode scheme, with
+    /// [`Scheme::Auto`] resolved from the release number. Shared by the initial
+    /// open and the reconnection path.
+    fn open_device(key: [u8; 8], scheme: Scheme) -> Result<(HidDevice, Scheme)> {
+        let hidapi = HidApi::new()?;
 
         let device = hidapi.open(VID, PID)?;
 
         let info = device.get_device_info().unwrap();
         let release_number = info.release_number();
         info!("Device: release-number = {:#04x}", release_number);
-        let decode = if release_number > 0x0100 {
-            false
-        } else {
-            true
-        };
 
-        let key = options.key;
+        /* Resolve the auto heuristic once, so reconnection keeps the scheme. */
+        let scheme = match scheme {
+            Scheme::Auto if release_number > 0x0100 => Scheme::Raw,
+            Scheme::Auto => Scheme::KeyedZyAura,
+            other => other,
+        };
 
         let frame = {
             let mut frame = [0; 9];
@@ -113,15 +192,31 @@ impl Sensor {
             // TODO - process send feature error...
         }
 
-        let debug = options.debug;
+        Ok((device, scheme))
+    }
 
-        Ok(Self {
-            dev: device,
-            debug: debug,
-            decode: decode,
-            key: key,
-            timeout: None,
-        })
+    /// Re-enumerate and reopen the device, blocking until it reappears.
+    ///
+    /// Called when a read fails on a resilient sensor (see
+    /// [`OpenOptions::reconnect`]); retries once per
+    /// [`retry_interval`][OpenOptions::retry_interval] until the device can be
+    /// reopened and its init frame re-sent.
+    fn reopen(&mut self) {
+        loop {
+            warn!("Device read failed, reconnecting in {:?}", self.retry_interval);
+            std::thread::sleep(self.retry_interval);
+            match Self::open_device(self.key, self.scheme) {
+                Ok((device, scheme)) => {
+                    info!("Device reconnected");
+                    self.dev = device;
+                    self.scheme = scheme;
+                    return;
+                }
+                Err(err) => {
+                    debug!("reconnect failed: {:?}", err);
+                }
+            }
+        }
     }
 
     pub fn read(&mut self) -> Option<AirQulityEvent> {
@@ -138,15 +233,19 @@ impl Sensor {
                 debug!("read_timeout: size = {:?}", size);
                 return Some(AirQulityEvent::WrongPacket);
             }
+        } else if self.reconnect {
+            self.reopen();
+            return None;
         } else {
             return None;
         }
 
         /* Step 2. Decode */
-        let data = if self.decode {
-            decrypt(data, self.key)
-        } else {
-            data
+        let data = match self.scheme {
+            Scheme::KeyedZyAura => decrypt(data, self.key),
+            Scheme::PermutationOnly => permutation_decrypt(data),
+            /* `Auto` is resolved at open time; treat it as raw defensively. */
+            Scheme::Raw | Scheme::Auto => data,
         };
 
         /* Check error message */
@@ -208,6 +307,15 @@ impl Sensor {
         }
     }
 
+    /// Consume the sensor and turn it into an asynchronous [`AirQualityStream`].
+    ///
+    /// The blocking `read()` loop runs on a dedicated OS thread and each event
+    /// is delivered through the returned stream.
+    #[cfg(feature = "stream")]
+    pub fn into_stream(self) -> AirQualityStream {
+        AirQualityStream::new(self)
+    }
+
 }
 
 #[derive(Debug, Clone)]
@@ -216,6 +324,9 @@ pub struct OpenOptions {
     key: [u8; 8],
     debug: bool,
     timeout: Option<Duration>,
+    reconnect: bool,
+    retry_interval: Duration,
+    scheme: Scheme,
 }
 
 impl Default for OpenOptions {
@@ -231,6 +342,9 @@ impl OpenOptions {
             key: [0; 8],
             debug: false,
             timeout: Some(Duration::from_secs(5)),
+            reconnect: false,
+            retry_interval: Duration::from_secs(1),
+            scheme: Scheme::Auto,
         }
     }
 
@@ -249,8 +363,52 @@ impl OpenOptions {
         self
     }
 
+    /// Transparently re-enumerate and reopen the device when a read fails,
+    /// instead of giving up. Useful for long-running logging daemons that must
+    /// survive hub resets and sleep/wake cycles.
+    pub fn reconnect(&mut self, yesno: bool) -> &mut Self {
+        self.reconnect = yesno;
+        self
+    }
+
+    /// Backoff between reconnection attempts (defaults to one second).
+    pub fn retry_interval(&mut self, interval: Duration) -> &mut Self {
+        self.retry_interval = interval;
+        self
+    }
+
+    /// Force a particular decode [`Scheme`], overriding the release-number
+    /// heuristic. Use [`Scheme::PermutationOnly`] for zero-key units.
+    pub fn scheme(&mut self, scheme: Scheme) -> &mut Self {
+        self.scheme = scheme;
+        self
+    }
+
     pub fn open(&self) -> Result<Sensor> {
         Sensor::open(self)
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Inverse of [`permutation_decrypt`]: re-apply the disjoint swaps and
+    /// undo the 3-bit right rotation, so a decoded frame can be scrambled back
+    /// into wire form for a round-trip check.
+    fn scramble(plain: [u8; 8]) -> [u8; 8] {
+        let mut data = u64::from_be_bytes(plain).rotate_left(3).to_be_bytes();
+        data.swap(0, 2);
+        data.swap(1, 4);
+        data.swap(3, 7);
+        data.swap(5, 6);
+        data
+    }
+
+    #[test]
+    fn permutation_round_trip() {
+        let frame = [0x50, 0x01, 0xf6, 0x47, 0x0d, 0x00, 0x00, 0x00];
+        assert_eq!(permutation_decrypt(scramble(frame)), frame);
+    }
+}
@@ -1,100 +1,196 @@
 
 use std::env;
-use std::time::Duration;
+use std::fs;
+use std::io::Write;
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
 
-use log::{LevelFilter, debug};
+use log::{LevelFilter, debug, warn};
 use env_logger::Builder;
 
-use telegraf::{Client, Point};
-
 use rs_co2mon::AirQulityEvent;
+use rs_co2mon::Config;
 use rs_co2mon::OpenOptions;
 use rs_co2mon::AirQulityEvent::AmbientTemperature;
 use rs_co2mon::AirQulityEvent::RelativeConcentration;
+use rs_co2mon::AirQulityEvent::Humidity;
+
+/* Configuration file consulted when no --config path is given. */
+const DEFAULT_CONFIG: &str = "co2mon.toml";
+
+/* Flush the batch once this many points have accumulated ... */
+const BATCH_SIZE: usize = 8;
+/* ... or once this much time has elapsed since the last flush. */
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
 
 fn report(mon: &mut Monitor, event: &AirQulityEvent) {
 
-    if let Some(ref mut conn) = mon.telegraf_client {
+    if mon.socket_addr.is_none() {
+        return;
+    }
 
     match *event {
-        AmbientTemperature { temp } => {
-                    let p = Point::new(
-                        String::from("co2monitor"),
-                        vec![
-//                            (String::from("name"), String::from(""))
-                        ],
-                        vec![
-                            (String::from("ambient_temperature"), Box::new(temp)),
-                        ],
-                        None,
-                    );
-                    conn.write_point(&p).unwrap();
-                },
-                RelativeConcentration { value } => {
-                    let p = Point::new(
-                        String::from("co2monitor"),
-                        vec![
-//                            (String::from("name"), String::from("relative_concentration"))
-                        ],
-                        vec![
-                            (String::from("relative_concentration"), Box::new(value)),
-                        ],
-                        None,
-                    );
-                    conn.write_point(&p).unwrap();
-                },
-                _ => {
-
-                },
-            }
+        AmbientTemperature { temp } if mon.forwards("AmbientTemperature") => {
+            mon.buffer_point("ambient_temperature", temp);
+        },
+        RelativeConcentration { value } if mon.forwards("RelativeConcentration") => {
+            /* Trailing `i` keeps the field an integer in line protocol. */
+            mon.buffer_point("relative_concentration", format_args!("{}i", value));
+        },
+        Humidity { value } if mon.forwards("Humidity") => {
+            mon.buffer_point("humidity", value);
+        },
+        _ => {
+
+        },
     }
+
+    mon.maybe_flush();
 }
 
 struct Monitor {
-    telegraf_enable: bool,           /* Delivery metric on Telegraf proxy */
-    telegraf_client: Option<Client>, /* Telegraf client                   */
-    enable_debug: bool,              /* Show debug output                 */
+    socket_addr: Option<String>, /* Telegraf endpoint, None disables  */
+    socket: Option<TcpStream>,   /* Telegraf line-protocol socket     */
+    forward: Vec<String>,        /* Variant allow-list, empty = all   */
+    buffer: String,              /* Pending line-protocol payload     */
+    pending: usize,              /* Points buffered since last flush  */
+    last_flush: Instant,         /* When the batch was last flushed   */
+    enable_debug: bool,          /* Show debug output                 */
 }
 
 impl Monitor {
-    fn new() -> Self {
+    fn new(config: &Config) -> Self {
         Monitor {
-            telegraf_enable: false,
-            telegraf_client: None,
-            enable_debug: false,
+            socket_addr: config.telegraf.clone(),
+            socket: None,
+            forward: config.forward.clone(),
+            buffer: String::new(),
+            pending: 0,
+            last_flush: Instant::now(),
+            enable_debug: config.debug,
         }
     }
-}
 
-fn main() {
+    /// Whether the given [`AirQulityEvent`] variant should be forwarded. An
+    /// empty allow-list forwards everything.
+    fn forwards(&self, variant: &str) -> bool {
+        self.forward.is_empty() || self.forward.iter().any(|v| v == variant)
+    }
 
-    let mut mon: Monitor = Monitor::new();
+    /// Connect to the Telegraf socket listener with Nagle disabled so that a
+    /// flush leaves the host immediately instead of being coalesced.
+    fn connect(&mut self) {
+        let addr = match self.socket_addr {
+            Some(ref addr) => addr.clone(),
+            None => return,
+        };
+        match TcpStream::connect(&addr) {
+            Ok(stream) => {
+                if let Err(err) = stream.set_nodelay(true) {
+                    warn!("Unable to disable Nagle: {:?}", err);
+                }
+                self.socket = Some(stream);
+            },
+            Err(err) => {
+                warn!("Unable to connect to Telegraf: {:?}", err);
+            },
+        }
+    }
 
-    /* Step 1. Parse arguments*/
-    for argument in env::args() {
-        if argument == "--debug" {
-            mon.enable_debug = true;
+    /// Append a single measurement to the pending batch as one line-protocol
+    /// record. All channels share the `co2monitor` measurement.
+    fn buffer_point(&mut self, field: &str, value: impl std::fmt::Display) {
+        self.buffer.push_str("co2monitor ");
+        self.buffer.push_str(field);
+        self.buffer.push('=');
+        self.buffer.push_str(&value.to_string());
+        self.buffer.push('\n');
+        self.pending += 1;
+    }
+
+    /// Flush the batch once enough points have accumulated or the flush
+    /// interval has elapsed.
+    fn maybe_flush(&mut self) {
+        if self.pending >= BATCH_SIZE || self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.flush();
         }
-        if argument == "--telegraf" {
-            mon.telegraf_enable = true;
-            mon.telegraf_client = Some(Client::new("tcp://127.0.0.1:8094").unwrap());
+    }
+
+    /// Write the whole accumulated payload in a single socket write.
+    fn flush(&mut self) {
+        if self.pending == 0 {
+            self.last_flush = Instant::now();
+            return;
         }
+        if self.socket.is_none() {
+            self.connect();
+        }
+        if let Some(ref mut stream) = self.socket {
+            if let Err(err) = stream.write_all(self.buffer.as_bytes()) {
+                warn!("Telegraf write failed, dropping socket: {:?}", err);
+                self.socket = None;
+            }
+        }
+        self.buffer.clear();
+        self.pending = 0;
+        self.last_flush = Instant::now();
+    }
+}
+
+/// Load the configuration from `path`, falling back to the built-in defaults
+/// when the file is absent. A missing file is silent for the implicit default
+/// path but warned about when the user pointed us at it with `--config`.
+fn load_config(path: &str, explicit: bool) -> Config {
+    match fs::read_to_string(path) {
+        Ok(contents) => toml::from_str(&contents).unwrap_or_else(|err| {
+            warn!("Invalid config {}: {:?}, using defaults", path, err);
+            Config::default()
+        }),
+        Err(err) => {
+            if explicit {
+                warn!("Unable to read config {}: {:?}, using defaults", path, err);
+            }
+            Config::default()
+        },
     }
+}
+
+fn main() {
 
-    /* Step 2. Initialize debug system */
-    if mon.enable_debug {
-        Builder::new().filter_level(LevelFilter::Debug).init();
+    /* Step 1. Parse arguments and load the configuration */
+    let mut config_path: Option<String> = None;
+    let mut force_debug = false;
+    let mut args = env::args().skip(1);
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            "--debug" => force_debug = true,
+            "--config" => config_path = args.next(),
+            _ => {},
+        }
     }
 
-    /* Step 3. Create Air Quality Monitor */
-    let mut sensor = OpenOptions::new()
-        .with_key([0x62, 0xea, 0x1d, 0x4f, 0x14, 0xfa, 0xe5, 0x6c])
-        .timeout(Some(Duration::from_secs(5)))
-        .debug(mon.enable_debug)
+    /* Step 2. Initialize the logger before loading the config, so that the
+     * config warnings below are actually emitted. Default to `warn` level and
+     * raise to `debug` when asked on the command line. */
+    let level = if force_debug { LevelFilter::Debug } else { LevelFilter::Warn };
+    Builder::new().filter_level(level).init();
+
+    /* Step 3. Load the configuration */
+    let explicit = config_path.is_some();
+    let path = config_path.unwrap_or_else(|| DEFAULT_CONFIG.to_string());
+    let mut config = load_config(&path, explicit);
+    if force_debug {
+        config.debug = true;
+    }
+
+    let mut mon: Monitor = Monitor::new(&config);
+
+    /* Step 4. Create Air Quality Monitor */
+    let mut sensor = OpenOptions::from_config(&config)
         .open()
         .unwrap();
 
-    /* Step 4. Process sensor monitoring */
+    /* Step 5. Process sensor monitoring */
     loop {
         if let Some(event) = sensor.read() {
             if mon.enable_debug {
@@ -0,0 +1,59 @@
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+use futures::Stream;
+use tokio::sync::mpsc::{self, Receiver};
+
+use crate::{AirQulityEvent, Error, Result, Sensor};
+
+/* Channel depth; `blocking_send` applies backpressure once this many events
+ * are queued, so a lagging consumer cannot make the producer grow memory. */
+const CHANNEL_CAPACITY: usize = 16;
+
+/* Backoff after a genuine read failure, so an unplugged/erroring device does
+ * not spin the producer thread at 100% CPU. */
+const ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+/// A [`Stream`] of [`AirQulityEvent`]s produced by a [`Sensor`].
+///
+/// Because `hidapi`'s `read_timeout` is blocking, the sensor is moved onto a
+/// dedicated OS thread that keeps calling [`Sensor::read`] and forwards every
+/// event through a channel; the stream simply polls the receiving end.
+pub struct AirQualityStream {
+    rx: Receiver<Result<AirQulityEvent>>,
+}
+
+impl AirQualityStream {
+    pub(crate) fn new(mut sensor: Sensor) -> Self {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        thread::spawn(move || loop {
+            /* `read()` folds the protocol-level outcomes into its event enum;
+             * a bare `None` is the only genuine read failure (the blocking
+             * `read_timeout` errored), which maps onto `InvalidMessage`. */
+            let item = match sensor.read() {
+                Some(event) => Ok(event),
+                None => {
+                    /* Back off so a dead handle does not busy-loop. */
+                    thread::sleep(ERROR_BACKOFF);
+                    Err(Error::InvalidMessage)
+                }
+            };
+            if tx.blocking_send(item).is_err() {
+                /* Receiver dropped, the stream is gone. */
+                break;
+            }
+        });
+        Self { rx }
+    }
+}
+
+impl Stream for AirQualityStream {
+    type Item = Result<AirQulityEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}